@@ -0,0 +1,31 @@
+/// A set of squares, bit n is set when the square with linear index n is in the set (so a1 is
+/// bit 0 and h8 is bit 63)
+pub type Bitboard = u64;
+
+/// Every dark square on the board
+///
+/// By convention a1 is a dark square ("light on the right" for both players), so squares
+/// alternate starting with dark on ranks 1, 3, 5, 7 (0x55 for each of those rank's bytes) and
+/// starting with light on ranks 2, 4, 6, 8 (0xAA).
+///
+/// BOARD_WIDTH and BOARD_HEIGHT dependent
+pub const DARK_SQUARES_BITBOARD: Bitboard = 0xAA55AA55AA55AA55;
+
+pub fn dark_squares() -> Bitboard { DARK_SQUARES_BITBOARD }
+pub fn light_squares() -> Bitboard { !DARK_SQUARES_BITBOARD }
+
+#[cfg(test)]
+mod tests {
+    use crate::board::bitboard::{dark_squares, light_squares};
+
+    #[test]
+    fn test_square_color_masks() {
+        assert_eq!(dark_squares().count_ones(), 32);
+        assert_eq!(light_squares().count_ones(), 32);
+        assert_eq!(dark_squares() & light_squares(), 0);
+
+        // a1 (bit 0) is dark and h1 (bit 7) is light
+        assert_eq!(dark_squares() & 1, 1);
+        assert_eq!(light_squares() & (1 << 7), 1 << 7);
+    }
+}
@@ -40,6 +40,18 @@ pub enum CoordinateError {
 
 type Result<T> = std::result::Result<T, CoordinateError>;
 
+/// Converts x-y coordinates to a linear index, usable in `const` contexts such as lookup
+/// tables. No bounds checking is done, callers must pass coordinates on the board.
+pub const fn xy_to_linear(x: u8, y: u8) -> u8 {
+    x + BOARD_WIDTH * y
+}
+
+/// Converts a linear index to (x, y) coordinates, usable in `const` contexts such as lookup
+/// tables. No bounds checking is done, callers must pass an index on the board.
+pub const fn linear_to_xy(index: u8) -> (u8, u8) {
+    (index % BOARD_WIDTH, index / BOARD_WIDTH)
+}
+
 impl CoordinateXY {
     pub fn new(x: u8, y: u8) -> Result<CoordinateXY> {
         if x >= BOARD_WIDTH || y >= BOARD_HEIGHT {
@@ -48,8 +60,33 @@ impl CoordinateXY {
 
         Ok(CoordinateXY { x, y })
     }
-    pub fn x(&self) -> u8 { self.x }
-    pub fn y(&self) -> u8 { self.y }
+    pub const fn x(&self) -> u8 { self.x }
+    pub const fn y(&self) -> u8 { self.y }
+
+    /// Compares against a coordinate in any representation by converting it to XY first
+    pub fn same_square_as<T: Into<CoordinateXY>>(&self, other: T) -> bool {
+        *self == other.into()
+    }
+
+    /// Checks whether this coordinate lies within the rectangle with corners `min` and `max`
+    /// (inclusive), where `min` is the lower left corner and `max` the upper right
+    pub fn in_region(&self, min: CoordinateXY, max: CoordinateXY) -> bool {
+        (min.x..=max.x).contains(&self.x) && (min.y..=max.y).contains(&self.y)
+    }
+
+    /// Lists every coordinate within the rectangle with corners `min` and `max` (inclusive),
+    /// ordered by rank then file
+    pub fn region_squares(min: CoordinateXY, max: CoordinateXY) -> Vec<CoordinateXY> {
+        let mut squares = Vec::new();
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                squares.push(CoordinateXY { x, y });
+            }
+        }
+
+        squares
+    }
 }
 
 impl CoordinateLinear {
@@ -61,16 +98,17 @@ impl CoordinateLinear {
         Ok(CoordinateLinear { index })
     }
 
-    pub fn index(&self) -> u8 { self.index }
+    pub const fn index(&self) -> u8 { self.index }
 }
 
 impl CoordinateAlgebraic {
+    /// Uppercase files are accepted and stored lowercase, anything that isn't a file 'a'-'h'
+    /// and rank '1'-'8' is rejected as BadFormat
     pub fn new(file: char, rank: char) -> Result<CoordinateAlgebraic> {
-        let x = file as u8 - 97;
-        let y = rank as u8 - 49;
+        let file = file.to_ascii_lowercase();
 
-        if x >= BOARD_WIDTH || y >= BOARD_HEIGHT {
-            return Err(CoordinateError::OutOfBounds)
+        if !('a'..='h').contains(&file) || !('1'..='8').contains(&rank) {
+            return Err(CoordinateError::BadFormat)
         }
 
         Ok(CoordinateAlgebraic { file, rank })
@@ -80,12 +118,43 @@ impl CoordinateAlgebraic {
     pub fn rank(&self) -> char { self.rank }
 }
 
+impl std::fmt::Display for CoordinateXY {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl std::fmt::Display for CoordinateAlgebraic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.file, self.rank)
+    }
+}
+
+impl std::str::FromStr for CoordinateAlgebraic {
+    type Err = CoordinateError;
+
+    /// Parses a two character square such as "e4", anything else is BadFormat
+    fn from_str(s: &str) -> Result<CoordinateAlgebraic> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => CoordinateAlgebraic::new(file, rank),
+            _ => Err(CoordinateError::BadFormat),
+        }
+    }
+}
+
+impl std::fmt::Display for CoordinateLinear {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.index)
+    }
+}
+
 /// From pure Coordinate type to other subtypes
 impl From<CoordinateXY> for CoordinateLinear {
     fn from(coord: CoordinateXY) -> CoordinateLinear {
         // converting from x-y to linear coordinates is simple: x + 8y
         // we can unwrap the result as any valid Coordinate type can be converted to another
-        CoordinateLinear::new(coord.x + 8 * coord.y).unwrap()
+        CoordinateLinear::new(xy_to_linear(coord.x, coord.y)).unwrap()
     }
 }
 
@@ -109,8 +178,7 @@ impl From<CoordinateXY> for CoordinateAlgebraic {
 impl From<CoordinateLinear> for CoordinateXY {
     fn from(coord: CoordinateLinear) -> CoordinateXY {
         // this conversion is dependent on the board size/shape, but it's just basic div/mod math
-        let y = coord.index / BOARD_HEIGHT;
-        let x = coord.index % BOARD_WIDTH;
+        let (x, y) = linear_to_xy(coord.index);
 
         // we can unwrap the result as any valid Coordinate type can be converted to another
         CoordinateXY::new(x, y).unwrap()
@@ -120,9 +188,8 @@ impl From<CoordinateLinear> for CoordinateXY {
 impl From<CoordinateAlgebraic> for CoordinateXY {
     fn from(coord: CoordinateAlgebraic) -> CoordinateXY {
         // probably the most complex conversion (from an "I barely know what I'm doing
-        // in Rust" standpoint at least). As this is a low level function which I suspect
-        // might get a lot of use, I am going to assume the inputs to this function are valid
-        // CoordinateAlgebraic characters
+        // in Rust" standpoint at least). CoordinateAlgebraic::new rejects anything outside of
+        // 'a'-'h' and '1'-'8', so the character math below can't underflow or leave the board
         //
         // we need to map ('a'-'h' == 0-7) and ('1'-'8' == 0-7)
         //                  97-104               49-56
@@ -157,6 +224,7 @@ impl From<CoordinateAlgebraic> for CoordinateLinear {
 #[cfg(test)]
 mod tests {
     use crate::board::coordinate::{CoordinateAlgebraic, CoordinateLinear, CoordinateXY, CoordinateError};
+    use crate::board::coordinate::{linear_to_xy, xy_to_linear};
     use crate::board::{BOARD_WIDTH, BOARD_HEIGHT};
 
     static TEST_SET: [((u8, u8), (char, char), u8); 24] = [
@@ -263,17 +331,129 @@ mod tests {
         assert_eq!(CoordinateXY::new(BOARD_WIDTH, BOARD_HEIGHT - 1).unwrap_err(), CoordinateError::OutOfBounds);
         assert_eq!(CoordinateXY::new(BOARD_WIDTH - 1, BOARD_HEIGHT).unwrap_err(), CoordinateError::OutOfBounds);
         assert_eq!(CoordinateXY::new(BOARD_WIDTH, BOARD_HEIGHT).unwrap_err(), CoordinateError::OutOfBounds);
+
+        // one past the edge on either axis, and far off the board
+        assert_eq!(CoordinateXY::new(8, 0).unwrap_err(), CoordinateError::OutOfBounds);
+        assert_eq!(CoordinateXY::new(0, 8).unwrap_err(), CoordinateError::OutOfBounds);
+        assert_eq!(CoordinateXY::new(255, 255).unwrap_err(), CoordinateError::OutOfBounds);
+
+        // the far corner is still on the board
+        assert!(CoordinateXY::new(7, 7).is_ok());
     }
 
     #[test]
     fn test_oob_construct_linear() {
         assert_eq!(CoordinateLinear::new(BOARD_WIDTH * BOARD_HEIGHT).unwrap_err(), CoordinateError::OutOfBounds);
+
+        // the last square is 63, one past it is the first invalid index
+        assert!(CoordinateLinear::new(63).is_ok());
+        assert_eq!(CoordinateLinear::new(64).unwrap_err(), CoordinateError::OutOfBounds);
+        assert_eq!(CoordinateLinear::new(255).unwrap_err(), CoordinateError::OutOfBounds);
     }
 
     #[test]
     fn test_oob_construct_algebraic() {
-        assert_eq!(CoordinateAlgebraic::new('i', '9').unwrap_err(), CoordinateError::OutOfBounds);
-        assert_eq!(CoordinateAlgebraic::new('h', '9').unwrap_err(), CoordinateError::OutOfBounds);
-        assert_eq!(CoordinateAlgebraic::new('i', '8').unwrap_err(), CoordinateError::OutOfBounds);
+        assert_eq!(CoordinateAlgebraic::new('i', '9').unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(CoordinateAlgebraic::new('h', '9').unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(CoordinateAlgebraic::new('i', '8').unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(CoordinateAlgebraic::new('i', '1').unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(CoordinateAlgebraic::new('a', '0').unwrap_err(), CoordinateError::BadFormat);
+
+        // the edges of the board are valid, one character either side of them is not
+        assert!(CoordinateAlgebraic::new('a', '1').is_ok());
+        assert!(CoordinateAlgebraic::new('h', '8').is_ok());
+        assert_eq!(CoordinateAlgebraic::new('`', '1').unwrap_err(), CoordinateError::BadFormat);
+    }
+
+    #[test]
+    fn test_algebraic_case() {
+        let lower = CoordinateAlgebraic::new('e', '4').unwrap();
+        let upper = CoordinateAlgebraic::new('E', '4').unwrap();
+
+        // uppercase files are normalized, so both spellings are the same square
+        assert_eq!(lower, upper);
+        assert_eq!(upper.file(), 'e');
+        assert_eq!(CoordinateXY::from(upper), CoordinateXY::new(4, 3).unwrap());
+        assert_eq!(CoordinateAlgebraic::new('I', '1').unwrap_err(), CoordinateError::BadFormat);
+    }
+
+    #[test]
+    fn test_region() {
+        // the center of the board, d4 through e5
+        let min = CoordinateXY::new(3, 3).unwrap();
+        let max = CoordinateXY::new(4, 4).unwrap();
+
+        assert!(CoordinateXY::new(3, 3).unwrap().in_region(min, max));
+        assert!(CoordinateXY::new(4, 4).unwrap().in_region(min, max));
+        assert!(!CoordinateXY::new(2, 3).unwrap().in_region(min, max));
+        assert!(!CoordinateXY::new(4, 5).unwrap().in_region(min, max));
+
+        let center = CoordinateXY::region_squares(min, max);
+        assert_eq!(center.len(), 4);
+        assert!(center.iter().all(|coord| coord.in_region(min, max)));
+
+        // a region can also be a single file, e.g. the whole a file
+        let min = CoordinateXY::new(0, 0).unwrap();
+        let max = CoordinateXY::new(0, BOARD_HEIGHT - 1).unwrap();
+        assert_eq!(CoordinateXY::region_squares(min, max).len(), BOARD_HEIGHT as usize);
+    }
+
+    #[test]
+    fn test_const_conversion() {
+        // these are evaluated at compile time, which is the point of the helpers
+        const E4: u8 = xy_to_linear(4, 3);
+        const H8: (u8, u8) = linear_to_xy(63);
+
+        assert_eq!(E4, 28);
+        assert_eq!(H8, (7, 7));
+
+        for ((x, y), _, linear) in TEST_SET.iter() {
+            assert_eq!(xy_to_linear(*x, *y), *linear);
+            assert_eq!(linear_to_xy(*linear), (*x, *y));
+        }
+    }
+
+    #[test]
+    fn test_conversions_infallible() {
+        // every coordinate that can be constructed converts to the other representations and
+        // back without panicking
+        for index in 0..(BOARD_WIDTH * BOARD_HEIGHT) {
+            let linear = CoordinateLinear::new(index).unwrap();
+            let xy = CoordinateXY::from(linear);
+            let algebraic = CoordinateAlgebraic::from(xy);
+
+            assert_eq!(CoordinateLinear::from(algebraic), linear);
+            assert_eq!(CoordinateXY::from(algebraic), xy);
+            assert_eq!(CoordinateAlgebraic::from(linear), algebraic);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", CoordinateAlgebraic::new('e', '4').unwrap()), "e4");
+        assert_eq!(format!("{}", CoordinateXY::new(4, 3).unwrap()), "(4, 3)");
+        assert_eq!(format!("{}", CoordinateLinear::new(28).unwrap()), "28");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("e4".parse::<CoordinateAlgebraic>().unwrap(), CoordinateAlgebraic::new('e', '4').unwrap());
+        assert_eq!("E4".parse::<CoordinateAlgebraic>().unwrap(), CoordinateAlgebraic::new('e', '4').unwrap());
+
+        assert_eq!("".parse::<CoordinateAlgebraic>().unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!("e".parse::<CoordinateAlgebraic>().unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!("e44".parse::<CoordinateAlgebraic>().unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!("4e".parse::<CoordinateAlgebraic>().unwrap_err(), CoordinateError::BadFormat);
+    }
+
+    #[test]
+    fn test_same_square_as() {
+        let e4 = CoordinateXY::new(4, 3).unwrap();
+
+        assert!(e4.same_square_as(CoordinateXY::new(4, 3).unwrap()));
+        assert!(e4.same_square_as(CoordinateAlgebraic::new('e', '4').unwrap()));
+        assert!(e4.same_square_as(CoordinateLinear::new(28).unwrap()));
+
+        assert!(!e4.same_square_as(CoordinateAlgebraic::new('d', '4').unwrap()));
     }
 }
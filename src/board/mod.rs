@@ -1,11 +1,123 @@
+pub mod bitboard;
 pub mod coordinate;
 
 pub use crate::piece::Piece;
 pub use coordinate::Coordinate;
-use crate::board::coordinate::CoordinateLinear;
+use crate::board::coordinate::{xy_to_linear, CoordinateAlgebraic, CoordinateError, CoordinateLinear};
+use crate::piece::{Color, Position, Rank};
 
 const BOARD_WIDTH: u8 = 8;
 const BOARD_HEIGHT: u8 = 8;
+const BOARD_SQUARES: usize = (BOARD_WIDTH * BOARD_HEIGHT) as usize;
+
+/// Size of the compact binary board encoding, each byte holds two squares
+const BOARD_BYTES: usize = BOARD_SQUARES / 2;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BoardError {
+    /// Serialized board data is not the expected size
+    BadLength,
+
+    /// Poorly specified input when attempting to instantiate a board
+    BadFormat,
+
+    /// The square at this linear index disagrees with its position on the board
+    InconsistentSquare(usize),
+}
+
+type Result<T> = std::result::Result<T, BoardError>;
+
+/// Positional bonuses, in centipawns (a pawn is worth 100)
+const ROOK_OPEN_FILE_BONUS: i32 = 20;
+const ROOK_HALF_OPEN_FILE_BONUS: i32 = 10;
+const ROOK_SEVENTH_RANK_BONUS: i32 = 20;
+const BISHOP_PAIR_BONUS: i32 = 30;
+
+/// The placement of every piece at the start of a standard game, as (linear square index,
+/// rank, color). This is the single source of truth for the starting layout.
+pub const START_POSITION: [(u8, Rank, Color); 32] = [
+    // White back rank
+    (xy_to_linear(0, 0), Rank::Rook, Color::White),
+    (xy_to_linear(1, 0), Rank::Knight, Color::White),
+    (xy_to_linear(2, 0), Rank::Bishop, Color::White),
+    (xy_to_linear(3, 0), Rank::Queen, Color::White),
+    (xy_to_linear(4, 0), Rank::King, Color::White),
+    (xy_to_linear(5, 0), Rank::Bishop, Color::White),
+    (xy_to_linear(6, 0), Rank::Knight, Color::White),
+    (xy_to_linear(7, 0), Rank::Rook, Color::White),
+
+    // White pawns
+    (xy_to_linear(0, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(1, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(2, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(3, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(4, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(5, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(6, 1), Rank::Pawn, Color::White),
+    (xy_to_linear(7, 1), Rank::Pawn, Color::White),
+
+    // Black pawns
+    (xy_to_linear(0, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(1, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(2, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(3, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(4, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(5, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(6, 6), Rank::Pawn, Color::Black),
+    (xy_to_linear(7, 6), Rank::Pawn, Color::Black),
+
+    // Black back rank
+    (xy_to_linear(0, 7), Rank::Rook, Color::Black),
+    (xy_to_linear(1, 7), Rank::Knight, Color::Black),
+    (xy_to_linear(2, 7), Rank::Bishop, Color::Black),
+    (xy_to_linear(3, 7), Rank::Queen, Color::Black),
+    (xy_to_linear(4, 7), Rank::King, Color::Black),
+    (xy_to_linear(5, 7), Rank::Bishop, Color::Black),
+    (xy_to_linear(6, 7), Rank::Knight, Color::Black),
+    (xy_to_linear(7, 7), Rank::Rook, Color::Black),
+];
+
+/// The eight compass directions a piece can travel in, North is towards rank 8
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The change in (x, y) from taking one step in this direction
+    pub fn offset(&self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+        }
+    }
+
+    /// Takes one step from `from` in this direction, None if that would leave the board
+    pub fn step(&self, from: Coordinate) -> Option<Coordinate> {
+        let (dx, dy) = self.offset();
+        let x = from.x() as i8 + dx;
+        let y = from.y() as i8 + dy;
+
+        if x.is_negative() || y.is_negative() {
+            return None;
+        }
+
+        Coordinate::new(x as u8, y as u8).ok()
+    }
+}
 
 #[derive(Debug, PartialEq)]
 enum SquareColor {
@@ -15,11 +127,9 @@ enum SquareColor {
 
 impl SquareColor {
     fn color_for_coordinate(coordinate: Coordinate) -> SquareColor {
-        // BOARD_WIDTH and BOARD_HEIGHT dependent
-        let bm_dark: u64 = 0xAA55AA55AA55AA55;
         let square_index = CoordinateLinear::from(coordinate).index();
 
-        if ((bm_dark >> square_index) & 1) != 0 {
+        if ((bitboard::DARK_SQUARES_BITBOARD >> square_index) & 1) != 0 {
             SquareColor::Dark
         } else {
             SquareColor::Light
@@ -27,6 +137,7 @@ impl SquareColor {
     }
 }
 
+#[derive(Debug, PartialEq)]
 pub struct Square {
     piece: Option<Piece>,
     color: SquareColor,
@@ -43,15 +154,538 @@ impl Square {
     }
 }
 
+/// Which castling moves each side is still allowed to make
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CastlingRights {
+    white_kingside: bool,
+    white_queenside: bool,
+    black_kingside: bool,
+    black_queenside: bool,
+}
+
+impl CastlingRights {
+    pub fn new(white_kingside: bool, white_queenside: bool,
+               black_kingside: bool, black_queenside: bool) -> CastlingRights {
+        CastlingRights { white_kingside, white_queenside, black_kingside, black_queenside }
+    }
+
+    pub fn white_kingside(&self) -> bool { self.white_kingside }
+    pub fn white_queenside(&self) -> bool { self.white_queenside }
+    pub fn black_kingside(&self) -> bool { self.black_kingside }
+    pub fn black_queenside(&self) -> bool { self.black_queenside }
+
+    /// The castling field of a FEN record, e.g. "KQkq", or "-" when no castling is allowed
+    fn to_fen_field(self) -> String {
+        let field: String = [
+            (self.white_kingside, 'K'),
+            (self.white_queenside, 'Q'),
+            (self.black_kingside, 'k'),
+            (self.black_queenside, 'q'),
+        ].iter().filter(|(allowed, _)| *allowed).map(|(_, symbol)| *symbol).collect();
+
+        if field.is_empty() { String::from("-") } else { field }
+    }
+}
+
+/// A single square whose occupant differs between two boards
+#[derive(Debug, PartialEq)]
+pub struct SquareDiff {
+    coordinate: Coordinate,
+    before: Option<Piece>,
+    after: Option<Piece>,
+}
+
+impl SquareDiff {
+    pub fn coordinate(&self) -> Coordinate { self.coordinate }
+    pub fn before(&self) -> Option<Piece> { self.before }
+    pub fn after(&self) -> Option<Piece> { self.after }
+}
+
+/// Every square whose occupant differs between two boards, in linear coordinate order
+#[derive(Debug, PartialEq)]
+pub struct BoardDiff {
+    squares: Vec<SquareDiff>,
+}
+
+impl BoardDiff {
+    pub fn squares(&self) -> &[SquareDiff] { &self.squares }
+    pub fn is_empty(&self) -> bool { self.squares.is_empty() }
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Board {
-    squares: [Square; (BOARD_HEIGHT * BOARD_WIDTH) as usize],
+    squares: [Square; BOARD_SQUARES],
+}
+
+impl Default for Board {
+    fn default() -> Board {
+        Board::new()
+    }
+}
+
+impl Board {
+    /// Creates a board with the pieces in their standard starting positions
+    pub fn new() -> Board {
+        Board::starting_position()
+    }
+
+    /// The standard starting position, white on ranks 1-2, black on ranks 7-8
+    pub fn starting_position() -> Board {
+        Board::with_placement(|coordinate| {
+            let index = CoordinateLinear::from(coordinate).index();
+            START_POSITION.iter()
+                .find(|(square, _, _)| *square == index)
+                .map(|(_, rank, color)| (*rank, *color))
+        })
+    }
+
+    /// Builds a board by asking `placement` which piece (if any) stands on each square
+    fn with_placement(mut placement: impl FnMut(Coordinate) -> Option<(Rank, Color)>) -> Board {
+        let squares = std::array::from_fn(|index| {
+            // we can unwrap as the array index is always within the board area
+            let coordinate = Coordinate::from(CoordinateLinear::new(index as u8).unwrap());
+            let piece = placement(coordinate).map(|(rank, color)| {
+                Piece::new(rank, color, Position::Board(coordinate))
+            });
+
+            Square::new(coordinate, piece)
+        });
+
+        Board { squares }
+    }
+
+    /// Checks that every square agrees with its position in the board: its coordinate maps
+    /// back to its index, its color matches its coordinate, and any piece on it knows it is
+    /// standing on that square
+    pub fn validate_invariants(&self) -> Result<()> {
+        for (index, square) in self.squares.iter().enumerate() {
+            let consistent = CoordinateLinear::from(square.coordinate).index() as usize == index
+                && square.color == SquareColor::color_for_coordinate(square.coordinate)
+                && square.piece.is_none_or(|piece| {
+                    piece.position() == Position::Board(square.coordinate)
+                });
+
+            if !consistent {
+                return Err(BoardError::InconsistentSquare(index));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn piece_at(&self, coordinate: Coordinate) -> Option<&Piece> {
+        let index = CoordinateLinear::from(coordinate).index() as usize;
+        self.squares[index].piece.as_ref()
+    }
+
+    /// Looks up the piece standing on an algebraic square such as "e1"
+    pub fn piece_at_str(&self, square: &str) -> std::result::Result<Option<&Piece>, CoordinateError> {
+        let algebraic = square.parse::<CoordinateAlgebraic>()?;
+        Ok(self.piece_at(Coordinate::from(algebraic)))
+    }
+
+    /// Walks from `from` in direction `dir` as a sliding piece belonging to `mover` would
+    ///
+    /// The walk stops at the edge of the board or at the first occupied square. An occupied
+    /// square is included if it holds an enemy piece (it can be captured), but not if it holds
+    /// one of the mover's own pieces. `from` itself is never included.
+    pub fn slide(&self, from: Coordinate, dir: Direction, mover: Color) -> Vec<Coordinate> {
+        let mut squares = Vec::new();
+        let mut current = from;
+
+        while let Some(next) = dir.step(current) {
+            match self.piece_at(next) {
+                Some(piece) => {
+                    if piece.color() != mover {
+                        squares.push(next);
+                    }
+                    break;
+                }
+                None => squares.push(next),
+            }
+
+            current = next;
+        }
+
+        squares
+    }
+
+    /// Lists the squares of the enemy pieces giving check to `color`'s king, two entries means
+    /// double check. A board without a king for `color` has no checkers.
+    pub fn checkers(&self, color: Color) -> Vec<Coordinate> {
+        let king = self.squares.iter()
+            .find(|square| match square.piece {
+                Some(piece) => piece.rank() == Rank::King && piece.color() == color,
+                None => false,
+            })
+            .map(|square| square.coordinate);
+
+        let king = match king {
+            Some(king) => king,
+            None => return Vec::new(),
+        };
+
+        let is_enemy = |coordinate: Coordinate, ranks: &[Rank]| match self.piece_at(coordinate) {
+            Some(piece) => piece.color() != color && ranks.contains(&piece.rank()),
+            None => false,
+        };
+
+        let mut checkers = Vec::new();
+
+        // sliders, the last square of a slide from the king is the first piece in that direction
+        let orthogonal = [Direction::North, Direction::East, Direction::South, Direction::West];
+        let diagonal = [Direction::NorthEast, Direction::SouthEast, Direction::SouthWest, Direction::NorthWest];
+
+        let sliders = [(orthogonal, [Rank::Rook, Rank::Queen]), (diagonal, [Rank::Bishop, Rank::Queen])];
+
+        for (directions, ranks) in sliders {
+            for dir in directions {
+                if let Some(&last) = self.slide(king, dir, color).last() {
+                    if is_enemy(last, &ranks) {
+                        checkers.push(last);
+                    }
+                }
+            }
+        }
+
+        // a knight jump is one orthogonal step followed by one diagonal step away from it
+        let jumps = [
+            (Direction::North, Direction::NorthEast), (Direction::North, Direction::NorthWest),
+            (Direction::East, Direction::NorthEast), (Direction::East, Direction::SouthEast),
+            (Direction::South, Direction::SouthEast), (Direction::South, Direction::SouthWest),
+            (Direction::West, Direction::SouthWest), (Direction::West, Direction::NorthWest),
+        ];
+
+        for (first, second) in jumps {
+            if let Some(square) = first.step(king).and_then(|square| second.step(square)) {
+                if is_enemy(square, &[Rank::Knight]) {
+                    checkers.push(square);
+                }
+            }
+        }
+
+        // enemy pawns attack the king from the squares diagonally in front of it
+        let pawn_directions = match color {
+            Color::White => [Direction::NorthEast, Direction::NorthWest],
+            Color::Black => [Direction::SouthEast, Direction::SouthWest],
+        };
+
+        for dir in pawn_directions {
+            if let Some(square) = dir.step(king) {
+                if is_enemy(square, &[Rank::Pawn]) {
+                    checkers.push(square);
+                }
+            }
+        }
+
+        checkers
+    }
+
+    /// Files (0 for the a file up to 7 for the h file) with no pawns of either color
+    pub fn open_files(&self) -> Vec<u8> {
+        (0..BOARD_WIDTH).filter(|file| self.pawns_on_file(*file).is_empty()).collect()
+    }
+
+    /// Files with no pawns belonging to `color` but at least one enemy pawn
+    pub fn half_open_files(&self, color: Color) -> Vec<u8> {
+        (0..BOARD_WIDTH)
+            .filter(|file| {
+                let pawns = self.pawns_on_file(*file);
+                !pawns.is_empty() && pawns.iter().all(|pawn| *pawn != color)
+            })
+            .collect()
+    }
+
+    /// The colors of the pawns standing on `file`
+    fn pawns_on_file(&self, file: u8) -> Vec<Color> {
+        // we can unwrap as both ends of the file are on the board
+        let min = Coordinate::new(file, 0).unwrap();
+        let max = Coordinate::new(file, BOARD_HEIGHT - 1).unwrap();
+
+        Coordinate::region_squares(min, max).into_iter()
+            .filter_map(|coordinate| self.piece_at(coordinate))
+            .filter(|piece| piece.rank() == Rank::Pawn)
+            .map(|piece| piece.color())
+            .collect()
+    }
+
+    /// Positional bonus for the rooks belonging to `color`
+    ///
+    /// Each rook scores for standing on an open file or, failing that, a half-open file, and
+    /// separately for standing on the opponent's second rank (the seventh from its own side).
+    pub fn rook_file_bonus(&self, color: Color) -> i32 {
+        let seventh_rank = match color {
+            Color::White => BOARD_HEIGHT - 2,
+            Color::Black => 1,
+        };
+
+        let open_files = self.open_files();
+        let half_open_files = self.half_open_files(color);
+        let mut bonus = 0;
+
+        for square in self.squares.iter() {
+            match square.piece {
+                Some(piece) if piece.rank() == Rank::Rook && piece.color() == color => (),
+                _ => continue,
+            }
+
+            let file = square.coordinate.x();
+            if open_files.contains(&file) {
+                bonus += ROOK_OPEN_FILE_BONUS;
+            } else if half_open_files.contains(&file) {
+                bonus += ROOK_HALF_OPEN_FILE_BONUS;
+            }
+
+            if square.coordinate.y() == seventh_rank {
+                bonus += ROOK_SEVENTH_RANK_BONUS;
+            }
+        }
+
+        bonus
+    }
+
+    /// Bonus for `color` holding the bishop pair when the opponent doesn't
+    pub fn bishop_pair_bonus(&self, color: Color) -> i32 {
+        let opponent = match color {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        };
+
+        if self.has_bishop_pair(color) && !self.has_bishop_pair(opponent) {
+            BISHOP_PAIR_BONUS
+        } else {
+            0
+        }
+    }
+
+    /// A pair means bishops on both dark and light squares, two bishops on the same color
+    /// (which only happens after a promotion) cover no more squares than one
+    fn has_bishop_pair(&self, color: Color) -> bool {
+        let bishops: Vec<&SquareColor> = self.squares.iter()
+            .filter(|square| match square.piece {
+                Some(piece) => piece.rank() == Rank::Bishop && piece.color() == color,
+                None => false,
+            })
+            .map(|square| &square.color)
+            .collect();
+
+        bishops.contains(&&SquareColor::Dark) && bishops.contains(&&SquareColor::Light)
+    }
+
+    /// Lists the squares whose occupant differs between this board (before) and `other` (after)
+    pub fn diff(&self, other: &Board) -> BoardDiff {
+        let squares = self.squares.iter().zip(other.squares.iter())
+            .filter(|(before, after)| before.piece != after.piece)
+            .map(|(before, after)| SquareDiff {
+                coordinate: before.coordinate,
+                before: before.piece,
+                after: after.piece,
+            })
+            .collect();
+
+        BoardDiff { squares }
+    }
+
+    /// Encodes the board using 4 bits per square, 32 bytes in total
+    ///
+    /// The low 3 bits of each nibble hold the rank of the piece on the square (0 for an empty
+    /// square) and the high bit is set for Black pieces. Squares are stored in linear order,
+    /// with the even square of each pair in the low nibble of its byte.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; BOARD_BYTES];
+
+        for (index, square) in self.squares.iter().enumerate() {
+            let nibble = match &square.piece {
+                Some(piece) => Self::piece_to_nibble(piece.rank(), piece.color()),
+                None => 0,
+            };
+
+            bytes[index / 2] |= nibble << (4 * (index % 2));
+        }
+
+        bytes
+    }
+
+    /// Decodes a board previously encoded with `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Board> {
+        if bytes.len() != BOARD_BYTES {
+            return Err(BoardError::BadLength);
+        }
+
+        // decode every square up front so that construction of the board itself can't fail
+        let mut placement = Vec::with_capacity(BOARD_SQUARES);
+        for index in 0..BOARD_SQUARES {
+            let nibble = (bytes[index / 2] >> (4 * (index % 2))) & 0xF;
+            placement.push(Self::nibble_to_piece(nibble)?);
+        }
+
+        Ok(Board::with_placement(|coordinate| {
+            placement[CoordinateLinear::from(coordinate).index() as usize]
+        }))
+    }
+
+    /// Renders the board as 8 rows of 8 space separated tokens with rank 8 at the top
+    ///
+    /// White pieces are uppercase letters, Black pieces lowercase, and empty squares are '.'
+    pub fn render_ascii(&self) -> String {
+        let mut diagram = String::new();
+
+        for y in (0..BOARD_HEIGHT).rev() {
+            let row: Vec<String> = (0..BOARD_WIDTH).map(|x| {
+                let index = xy_to_linear(x, y) as usize;
+                match &self.squares[index].piece {
+                    Some(piece) => piece.symbol(),
+                    None => '.',
+                }.to_string()
+            }).collect();
+
+            diagram.push_str(&row.join(" "));
+            diagram.push('\n');
+        }
+
+        diagram
+    }
+
+    /// Parses a board from the format produced by `render_ascii`
+    ///
+    /// Blank lines and surrounding whitespace are ignored so that diagrams can be written
+    /// as indented multi-line string literals.
+    pub fn from_ascii(diagram: &str) -> Result<Board> {
+        let rows: Vec<&str> = diagram.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if rows.len() != BOARD_HEIGHT as usize {
+            return Err(BoardError::BadFormat);
+        }
+
+        // the first row of the diagram is the highest rank, so walk the rows in reverse to
+        // produce squares in linear order
+        let mut placement = Vec::with_capacity(BOARD_SQUARES);
+        for row in rows.iter().rev() {
+            let tokens: Vec<&str> = row.split_whitespace().collect();
+            if tokens.len() != BOARD_WIDTH as usize {
+                return Err(BoardError::BadFormat);
+            }
+
+            for token in tokens {
+                let mut chars = token.chars();
+                let symbol = match (chars.next(), chars.next()) {
+                    (Some(symbol), None) => symbol,
+                    _ => return Err(BoardError::BadFormat),
+                };
+
+                placement.push(Self::symbol_to_piece(symbol)?);
+            }
+        }
+
+        Ok(Board::with_placement(|coordinate| {
+            placement[CoordinateLinear::from(coordinate).index() as usize]
+        }))
+    }
+
+    /// Produces a full FEN record from the pieces on this board plus the given game metadata,
+    /// which the board itself doesn't track
+    pub fn to_fen_with(&self, turn: Color, castling: CastlingRights, en_passant: Option<Coordinate>,
+                       halfmove: u32, fullmove: u32) -> String {
+        let mut ranks = Vec::with_capacity(BOARD_HEIGHT as usize);
+
+        // FEN lists rank 8 first, with runs of empty squares collapsed into a count
+        for y in (0..BOARD_HEIGHT).rev() {
+            let mut rank = String::new();
+            let mut empty = 0;
+
+            for x in 0..BOARD_WIDTH {
+                match &self.squares[xy_to_linear(x, y) as usize].piece {
+                    Some(piece) => {
+                        if empty > 0 {
+                            rank.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        rank.push(piece.symbol());
+                    }
+                    None => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                rank.push_str(&empty.to_string());
+            }
+
+            ranks.push(rank);
+        }
+
+        let turn = match turn {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+
+        let en_passant = match en_passant {
+            Some(coordinate) => CoordinateAlgebraic::from(coordinate).to_string(),
+            None => String::from("-"),
+        };
+
+        format!("{} {} {} {} {} {}", ranks.join("/"), turn, castling.to_fen_field(),
+                en_passant, halfmove, fullmove)
+    }
+
+    fn symbol_to_piece(symbol: char) -> Result<Option<(Rank, Color)>> {
+        if symbol == '.' {
+            return Ok(None);
+        }
+
+        let color = if symbol.is_ascii_uppercase() { Color::White } else { Color::Black };
+        match Rank::from_symbol(symbol.to_ascii_uppercase()) {
+            Some(rank) => Ok(Some((rank, color))),
+            None => Err(BoardError::BadFormat),
+        }
+    }
+
+    fn piece_to_nibble(rank: Rank, color: Color) -> u8 {
+        let rank_bits = match rank {
+            Rank::Pawn => 1,
+            Rank::Bishop => 2,
+            Rank::Knight => 3,
+            Rank::Rook => 4,
+            Rank::Queen => 5,
+            Rank::King => 6,
+        };
+
+        match color {
+            Color::White => rank_bits,
+            Color::Black => rank_bits | 0x8,
+        }
+    }
+
+    fn nibble_to_piece(nibble: u8) -> Result<Option<(Rank, Color)>> {
+        let color = if nibble & 0x8 != 0 { Color::Black } else { Color::White };
+
+        let rank = match nibble & 0x7 {
+            // an empty square must not have the color bit set either
+            0 if nibble == 0 => return Ok(None),
+            1 => Rank::Pawn,
+            2 => Rank::Bishop,
+            3 => Rank::Knight,
+            4 => Rank::Rook,
+            5 => Rank::Queen,
+            6 => Rank::King,
+            _ => return Err(BoardError::BadFormat),
+        };
+
+        Ok(Some((rank, color)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::board::{Coordinate, SquareColor};
+    use crate::board::{Board, BoardError, CastlingRights, Coordinate, Direction, SquareColor};
+    use crate::board::{BOARD_BYTES, START_POSITION};
+    use crate::board::{BISHOP_PAIR_BONUS, ROOK_HALF_OPEN_FILE_BONUS, ROOK_OPEN_FILE_BONUS, ROOK_SEVENTH_RANK_BONUS};
     use crate::board::SquareColor::{Dark, Light};
-    use crate::board::coordinate::CoordinateLinear;
+    use crate::board::coordinate::{CoordinateError, CoordinateLinear};
+    use crate::piece::{Color, Piece, Position, Rank};
+    use crate::test_support::assert_board_eq;
 
     #[test]
     fn test_color_determination() {
@@ -73,4 +707,495 @@ mod tests {
             assert_eq!(SquareColor::color_for_coordinate(coord), *expect);
         }
     }
+
+    #[test]
+    fn test_color_determination_linear_bounds() {
+        // only the 64 real squares can become a Coordinate through the linear path, so an off
+        // board index (which would otherwise map to y = 12 for index 100) can't reach
+        // color_for_coordinate at all
+        assert!(CoordinateLinear::new(63).is_ok());
+        assert!(CoordinateLinear::new(64).is_err());
+        assert!(CoordinateLinear::new(100).is_err());
+
+        let h8 = Coordinate::from(CoordinateLinear::new(63).unwrap());
+        assert_eq!(SquareColor::color_for_coordinate(h8), Dark);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        // place a white king on e1 (square 4), a white pawn on e2 (square 12) and a black
+        // king on e8 (square 60), two squares per byte with the even square in the low nibble
+        let mut bytes = vec![0; BOARD_BYTES];
+        bytes[2] = 0x06;
+        bytes[6] = 0x01;
+        bytes[30] = 0x0E;
+
+        let board = Board::from_bytes(&bytes).unwrap();
+        let king = board.squares[4].piece.unwrap();
+        assert_eq!(king.rank(), Rank::King);
+        assert_eq!(king.color(), Color::White);
+
+        let king = board.squares[60].piece.unwrap();
+        assert_eq!(king.rank(), Rank::King);
+        assert_eq!(king.color(), Color::Black);
+
+        assert!(board.squares[5].piece.is_none());
+        assert_eq!(board.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_bad_bytes() {
+        let bytes = vec![0; BOARD_BYTES];
+
+        assert_eq!(Board::from_bytes(&bytes[..BOARD_BYTES - 1]).unwrap_err(), BoardError::BadLength);
+        assert_eq!(Board::from_bytes(&[]).unwrap_err(), BoardError::BadLength);
+
+        // 7 is not a valid rank and a lone color bit is not a valid piece
+        let mut bad_rank = bytes.clone();
+        bad_rank[0] = 0x07;
+        assert_eq!(Board::from_bytes(&bad_rank).unwrap_err(), BoardError::BadFormat);
+
+        let mut bad_color = bytes;
+        bad_color[0] = 0x80;
+        assert_eq!(Board::from_bytes(&bad_color).unwrap_err(), BoardError::BadFormat);
+    }
+
+    static STARTING_DIAGRAM: &str = "
+        r n b q k b n r
+        p p p p p p p p
+        . . . . . . . .
+        . . . . . . . .
+        . . . . . . . .
+        . . . . . . . .
+        P P P P P P P P
+        R N B Q K B N R
+    ";
+
+    #[test]
+    fn test_ascii_round_trip() {
+        let board = Board::new();
+
+        // rendering the starting position should reproduce the diagram (minus the indentation)
+        let rendered = board.render_ascii();
+        let expect: Vec<&str> = STARTING_DIAGRAM.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+        assert_eq!(rendered.lines().collect::<Vec<&str>>(), expect);
+
+        // and parsing the rendered output should give back the same board
+        let parsed = Board::from_ascii(&rendered).unwrap();
+        assert_board_eq(&parsed, &board);
+
+        // spot check that uppercase is white, lowercase is black, and the top row is rank 8
+        let white_queen = parsed.squares[3].piece.unwrap();
+        assert_eq!(white_queen.rank(), Rank::Queen);
+        assert_eq!(white_queen.color(), Color::White);
+
+        let black_king = parsed.squares[60].piece.unwrap();
+        assert_eq!(black_king.rank(), Rank::King);
+        assert_eq!(black_king.color(), Color::Black);
+    }
+
+    #[test]
+    fn test_bad_ascii() {
+        // too few rows
+        let diagram = STARTING_DIAGRAM.replacen("R N B Q K B N R", "", 1);
+        assert_eq!(Board::from_ascii(&diagram).unwrap_err(), BoardError::BadFormat);
+
+        // too few tokens in a row
+        let diagram = STARTING_DIAGRAM.replacen("R N B Q K B N R", "R N B Q K B N", 1);
+        assert_eq!(Board::from_ascii(&diagram).unwrap_err(), BoardError::BadFormat);
+
+        // a token which isn't a piece
+        let diagram = STARTING_DIAGRAM.replacen("R N B Q K B N R", "R N B Q K B N X", 1);
+        assert_eq!(Board::from_ascii(&diagram).unwrap_err(), BoardError::BadFormat);
+
+        // a token longer than one character
+        let diagram = STARTING_DIAGRAM.replacen("R N B Q K B N R", "R N B Q K B N RR", 1);
+        assert_eq!(Board::from_ascii(&diagram).unwrap_err(), BoardError::BadFormat);
+    }
+
+    #[test]
+    fn test_piece_at_str() {
+        let board = Board::new();
+
+        let king = board.piece_at_str("e1").unwrap().unwrap();
+        assert_eq!(king.rank(), Rank::King);
+        assert_eq!(king.color(), Color::White);
+
+        assert!(board.piece_at_str("e4").unwrap().is_none());
+
+        assert_eq!(board.piece_at_str("A1").unwrap().unwrap().rank(), Rank::Rook);
+
+        assert_eq!(board.piece_at_str("z9").unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(board.piece_at_str("e").unwrap_err(), CoordinateError::BadFormat);
+        assert_eq!(board.piece_at_str("e11").unwrap_err(), CoordinateError::BadFormat);
+    }
+
+    #[test]
+    fn test_slide() {
+        let board = Board::from_ascii("
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . r . . . .
+            . . . . . . . .
+            . . . . . . . .
+            R . . N . . . .
+        ").unwrap();
+
+        let a1 = Coordinate::new(0, 0).unwrap();
+        let d1 = Coordinate::new(3, 0).unwrap();
+        let d4 = Coordinate::new(3, 3).unwrap();
+
+        // an open ray runs all the way to the edge of the board
+        let north = board.slide(a1, Direction::North, Color::White);
+        assert_eq!(north.len(), 7);
+        assert_eq!(north.last(), Some(&Coordinate::new(0, 7).unwrap()));
+
+        // nothing lies beyond the edge of the board
+        assert!(board.slide(a1, Direction::West, Color::White).is_empty());
+        assert!(board.slide(a1, Direction::SouthEast, Color::White).is_empty());
+
+        // a friendly blocker on d1 is excluded
+        let east = board.slide(a1, Direction::East, Color::White);
+        assert_eq!(east, vec![Coordinate::new(1, 0).unwrap(), Coordinate::new(2, 0).unwrap()]);
+
+        // an enemy blocker on d4 is included as a capture
+        let north = board.slide(d1, Direction::North, Color::White);
+        assert_eq!(north, vec![Coordinate::new(3, 1).unwrap(), Coordinate::new(3, 2).unwrap(), d4]);
+
+        // from the black rook's perspective the white knight is the capture
+        let south = board.slide(d4, Direction::South, Color::Black);
+        assert_eq!(south.last(), Some(&d1));
+    }
+
+    #[test]
+    fn test_checkers() {
+        // the rook on e8 checks down the open e file, the bishop on b4 is blocked by the
+        // knight on d2 and the knight on g3 doesn't reach e1
+        let board = Board::from_ascii("
+            . . . . r . . k
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . b . . . . . .
+            . . . . . . n .
+            . . . N . . . .
+            . . . . K . . .
+        ").unwrap();
+
+        let e8 = Coordinate::new(4, 7).unwrap();
+        assert_eq!(board.checkers(Color::White), vec![e8]);
+        assert!(board.checkers(Color::Black).is_empty());
+
+        // a knight on d3 gives a second check alongside the rook
+        let double = Board::from_ascii("
+            . . . . r . . k
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . n . . . .
+            . . . . . . . .
+            . . . . K . . .
+        ").unwrap();
+
+        let checkers = double.checkers(Color::White);
+        assert_eq!(checkers.len(), 2);
+        assert!(checkers.contains(&e8));
+        assert!(checkers.contains(&Coordinate::new(3, 2).unwrap()));
+
+        // a pawn only checks diagonally forward, from the king's point of view
+        let pawns = Board::from_ascii("
+            . . . . . . . k
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . p p . . .
+            . . . . K . . .
+        ").unwrap();
+        assert_eq!(pawns.checkers(Color::White), vec![Coordinate::new(3, 1).unwrap()]);
+
+        // there is no check in the starting position
+        assert!(Board::new().checkers(Color::White).is_empty());
+        assert!(Board::new().checkers(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_open_files() {
+        // the starting position with both e pawns and the white d pawn gone
+        let board = Board::from_ascii("
+            r n b q k b n r
+            p p p p . p p p
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            P P P . . P P P
+            R N B Q K B N R
+        ").unwrap();
+
+        assert_eq!(board.open_files(), vec![4]);
+        assert_eq!(board.half_open_files(Color::White), vec![3]);
+        assert!(board.half_open_files(Color::Black).is_empty());
+
+        // every file has pawns of both colors at the start
+        assert!(Board::new().open_files().is_empty());
+        assert!(Board::new().half_open_files(Color::White).is_empty());
+    }
+
+    #[test]
+    fn test_rook_file_bonus() {
+        // the a file is open, the d file is half-open for white, the h file rook is stuck
+        // behind its own pawn
+        let board = Board::from_ascii("
+            . . . . k . . .
+            . . . p . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . P
+            R . . R K . . R
+        ").unwrap();
+
+        let open = Board::from_ascii("
+            . . . . k . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            R . . . K . . .
+        ").unwrap();
+        let half_open = Board::from_ascii("
+            . . . . k . . .
+            . . . p . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . R K . . .
+        ").unwrap();
+        let blocked = Board::from_ascii("
+            . . . . k . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . P
+            . . . . K . . R
+        ").unwrap();
+
+        assert_gt!(open.rook_file_bonus(Color::White), half_open.rook_file_bonus(Color::White));
+        assert_gt!(half_open.rook_file_bonus(Color::White), blocked.rook_file_bonus(Color::White));
+        assert_eq!(blocked.rook_file_bonus(Color::White), 0);
+
+        // the bonus is the sum over every rook, and black has none
+        assert_eq!(board.rook_file_bonus(Color::White), ROOK_OPEN_FILE_BONUS + ROOK_HALF_OPEN_FILE_BONUS);
+        assert_eq!(board.rook_file_bonus(Color::Black), 0);
+
+        // a rook on the seventh scores on top of the file bonus, for black that is the second rank
+        let seventh = Board::from_ascii("
+            . . . . k . . .
+            R . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . r . . . . . .
+            . . . . K . . .
+        ").unwrap();
+        assert_eq!(seventh.rook_file_bonus(Color::White), ROOK_OPEN_FILE_BONUS + ROOK_SEVENTH_RANK_BONUS);
+        assert_eq!(seventh.rook_file_bonus(Color::Black), ROOK_OPEN_FILE_BONUS + ROOK_SEVENTH_RANK_BONUS);
+
+        // the starting position has no open files and no rooks on the seventh
+        assert_eq!(Board::from_ascii(STARTING_DIAGRAM).unwrap().rook_file_bonus(Color::White), 0);
+    }
+
+    #[test]
+    fn test_bishop_pair_bonus() {
+        // white has bishops on c1 and f1 against black's knight and bishop, equal material
+        let pair = Board::from_ascii("
+            . . n . k b . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . B . K B . .
+        ").unwrap();
+
+        // the same material with white holding a knight instead of the light squared bishop
+        let no_pair = Board::from_ascii("
+            . . n . k b . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . B . K N . .
+        ").unwrap();
+
+        assert_eq!(pair.bishop_pair_bonus(Color::White), BISHOP_PAIR_BONUS);
+        assert_eq!(pair.bishop_pair_bonus(Color::Black), 0);
+        assert_gt!(pair.bishop_pair_bonus(Color::White), no_pair.bishop_pair_bonus(Color::White));
+        assert_eq!(no_pair.bishop_pair_bonus(Color::White), 0);
+
+        // two bishops on the same color of square are not a pair
+        let same_color = Board::from_ascii("
+            . . . . k . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            B . B . K . . .
+        ").unwrap();
+        assert_eq!(same_color.bishop_pair_bonus(Color::White), 0);
+
+        // when both sides hold the pair neither gets the bonus
+        let start = Board::from_ascii(STARTING_DIAGRAM).unwrap();
+        assert_eq!(start.bishop_pair_bonus(Color::White), 0);
+        assert_eq!(start.bishop_pair_bonus(Color::Black), 0);
+    }
+
+    #[test]
+    fn test_diff() {
+        let start = Board::new();
+        assert!(start.diff(&Board::new()).is_empty());
+
+        // the board after 1. e4
+        let after_e4 = Board::from_ascii("
+            r n b q k b n r
+            p p p p p p p p
+            . . . . . . . .
+            . . . . . . . .
+            . . . . P . . .
+            . . . . . . . .
+            P P P P . P P P
+            R N B Q K B N R
+        ").unwrap();
+
+        let diff = start.diff(&after_e4);
+        assert_eq!(diff.squares().len(), 2);
+
+        // e2 was emptied
+        let e2 = &diff.squares()[0];
+        assert_eq!(e2.coordinate(), Coordinate::new(4, 1).unwrap());
+        assert_eq!(e2.before().unwrap().rank(), Rank::Pawn);
+        assert!(e2.after().is_none());
+
+        // e4 was filled
+        let e4 = &diff.squares()[1];
+        assert_eq!(e4.coordinate(), Coordinate::new(4, 3).unwrap());
+        assert!(e4.before().is_none());
+        assert_eq!(e4.after().unwrap().rank(), Rank::Pawn);
+        assert_eq!(e4.after().unwrap().color(), Color::White);
+    }
+
+    #[test]
+    fn test_start_position() {
+        assert_eq!(START_POSITION.len(), 32);
+
+        // every entry in the table is reflected on the new board...
+        let board = Board::new();
+        for (index, rank, color) in START_POSITION.iter() {
+            let coordinate = Coordinate::from(CoordinateLinear::new(*index).unwrap());
+            let piece = board.piece_at(coordinate).unwrap();
+
+            assert_eq!(piece.rank(), *rank);
+            assert_eq!(piece.color(), *color);
+        }
+
+        // ...and nothing else is on it
+        let occupied = board.squares.iter().filter(|square| square.piece.is_some()).count();
+        assert_eq!(occupied, START_POSITION.len());
+
+        // the table agrees with the hand-drawn diagram
+        assert_board_eq(&board, &Board::from_ascii(STARTING_DIAGRAM).unwrap());
+
+        // starting_position is the same board under its own name
+        let board = Board::starting_position();
+        assert_eq!(board, Board::new());
+
+        let rook = board.piece_at_str("a1").unwrap().unwrap();
+        assert_eq!(rook.rank(), Rank::Rook);
+        assert_eq!(rook.color(), Color::White);
+
+        let king = board.piece_at_str("e1").unwrap().unwrap();
+        assert_eq!(king.rank(), Rank::King);
+        assert_eq!(king.color(), Color::White);
+    }
+
+    #[test]
+    fn test_validate_invariants() {
+        assert_eq!(Board::new().validate_invariants(), Ok(()));
+        assert_eq!(Board::from_bytes(&Board::new().to_bytes()).unwrap().validate_invariants(), Ok(()));
+
+        // a square which thinks it is somewhere else
+        let mut board = Board::new();
+        board.squares[9].coordinate = Coordinate::new(0, 0).unwrap();
+        assert_eq!(board.validate_invariants(), Err(BoardError::InconsistentSquare(9)));
+
+        // a square with the wrong color
+        let mut board = Board::new();
+        board.squares[0].color = Light;
+        assert_eq!(board.validate_invariants(), Err(BoardError::InconsistentSquare(0)));
+
+        // a piece which thinks it is standing on another square
+        let mut board = Board::new();
+        let misplaced = Position::Board(Coordinate::new(4, 3).unwrap());
+        board.squares[12].piece = Some(Piece::new(Rank::Pawn, Color::White, misplaced));
+        assert_eq!(board.validate_invariants(), Err(BoardError::InconsistentSquare(12)));
+    }
+
+    #[test]
+    fn test_to_fen_with() {
+        let all_rights = CastlingRights::new(true, true, true, true);
+        assert_eq!(Board::new().to_fen_with(Color::White, all_rights, None, 0, 1),
+                   "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        // after 1. e4, with en passant available on e3
+        let after_e4 = Board::from_ascii("
+            r n b q k b n r
+            p p p p p p p p
+            . . . . . . . .
+            . . . . . . . .
+            . . . . P . . .
+            . . . . . . . .
+            P P P P . P P P
+            R N B Q K B N R
+        ").unwrap();
+        let e3 = Coordinate::new(4, 2).unwrap();
+        assert_eq!(after_e4.to_fen_with(Color::Black, all_rights, Some(e3), 0, 1),
+                   "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        // a puzzle with only some castling rights left, and one with none at all
+        let puzzle = Board::from_ascii("
+            r . . . k . . r
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . . . . .
+            . . . . K . . R
+        ").unwrap();
+        let some_rights = CastlingRights::new(true, false, false, true);
+        assert_eq!(puzzle.to_fen_with(Color::White, some_rights, None, 12, 40),
+                   "r3k2r/8/8/8/8/8/8/4K2R w Kq - 12 40");
+
+        let no_rights = CastlingRights::new(false, false, false, false);
+        assert_eq!(puzzle.to_fen_with(Color::Black, no_rights, None, 0, 1),
+                   "r3k2r/8/8/8/8/8/8/4K2R b - - 0 1");
+    }
 }
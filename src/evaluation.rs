@@ -0,0 +1,31 @@
+/// Centipawns per factor of ten in the odds of White winning, chosen so that being about a
+/// piece (400 centipawns) up gives White an expected score of roughly 0.76
+const WIN_PROBABILITY_SCALE: f64 = 800.0;
+
+/// Maps an evaluation in centipawns, from White's point of view, to White's expected score
+/// between 0 (certain loss) and 1 (certain win) using a logistic curve
+pub fn eval_to_win_probability(centipawns: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-(centipawns as f64) / WIN_PROBABILITY_SCALE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluation::eval_to_win_probability;
+
+    #[test]
+    fn test_eval_to_win_probability() {
+        // an equal position is a coin toss
+        assert_lt!((eval_to_win_probability(0) - 0.5).abs(), 0.001);
+
+        // about a piece up
+        assert_lt!((eval_to_win_probability(400) - 0.76).abs(), 0.01);
+
+        // the curve is symmetric, being a piece down is the mirror image
+        assert_lt!((eval_to_win_probability(-400) - 0.24).abs(), 0.01);
+
+        // a crushing advantage is all but certain, but never goes past 1
+        assert_gt!(eval_to_win_probability(5000), 0.99);
+        assert_le!(eval_to_win_probability(i32::MAX), 1.0);
+        assert_ge!(eval_to_win_probability(i32::MIN), 0.0);
+    }
+}
@@ -5,10 +5,14 @@ use clap::{Arg, App};
 use serde_json;
 
 pub mod board;
+pub mod evaluation;
 pub mod piece;
 pub mod serialization;
 pub mod timer;
 
+#[cfg(test)]
+mod test_support;
+
 fn main() {
     let matches = App::new("Chess Toolkit (Rust)")
         .version("0.1")
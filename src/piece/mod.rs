@@ -5,7 +5,7 @@ use super::board::{Coordinate};
 use serde::ser::SerializeStruct;
 use crate::board::coordinate::CoordinateAlgebraic;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Rank {
     Pawn,
     Bishop,
@@ -15,6 +15,40 @@ pub enum Rank {
     King,
 }
 
+impl Rank {
+    /// The letter used for this rank in standard notation, e.g. 'N' for a knight
+    pub fn symbol(&self) -> char {
+        match self {
+            Rank::Pawn => 'P',
+            Rank::Bishop => 'B',
+            Rank::Knight => 'N',
+            Rank::Rook => 'R',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+        }
+    }
+
+    /// The inverse of `symbol`, only uppercase letters are accepted
+    pub fn from_symbol(symbol: char) -> Option<Rank> {
+        match symbol {
+            'P' => Some(Rank::Pawn),
+            'B' => Some(Rank::Bishop),
+            'N' => Some(Rank::Knight),
+            'R' => Some(Rank::Rook),
+            'Q' => Some(Rank::Queen),
+            'K' => Some(Rank::King),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Color {
+    White,
+    Black,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Position {
     /// Piece was captured by the opponent
     Captured,
@@ -37,8 +71,7 @@ impl Serialize for Position {
             Position::Captured => { String::from("captured") },
             Position::OtherwiseOffBoard => { String::from("off") },
             Position::Board(coord) => {
-                let algebraic = CoordinateAlgebraic::from(*coord);
-                format!("{}{}", algebraic.file(), algebraic.rank())
+                CoordinateAlgebraic::from(*coord).to_string()
             }
         };
 
@@ -47,14 +80,29 @@ impl Serialize for Position {
     }
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Piece {
     rank: Rank,
+    color: Color,
     position: Position,
 }
 
 impl Piece {
-    pub fn new(rank: Rank, position: Position) -> Piece {
-        Piece { rank, position }
+    pub fn new(rank: Rank, color: Color, position: Position) -> Piece {
+        Piece { rank, color, position }
+    }
+
+    pub fn rank(&self) -> Rank { self.rank }
+    pub fn color(&self) -> Color { self.color }
+    pub fn position(&self) -> Position { self.position }
+
+    /// The letter for this piece in standard notation, uppercase for White and lowercase for
+    /// Black (e.g. 'N' for a white knight, 'n' for a black one)
+    pub fn symbol(&self) -> char {
+        match self.color {
+            Color::White => self.rank.symbol(),
+            Color::Black => self.rank.symbol().to_ascii_lowercase(),
+        }
     }
 }
 
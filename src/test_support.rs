@@ -0,0 +1,69 @@
+use crate::board::Board;
+use crate::board::coordinate::CoordinateAlgebraic;
+use crate::piece::Piece;
+
+/// Asserts that two boards are equal
+///
+/// On failure this panics with both boards drawn side by side and a list of every square whose
+/// occupant differs, which is much easier to read than the derived Debug output of a Board.
+#[track_caller]
+pub fn assert_board_eq(actual: &Board, expected: &Board) {
+    if actual == expected {
+        return;
+    }
+
+    let mut message = String::from("boards are not equal\n\nactual             expected\n");
+
+    let actual_ascii = actual.render_ascii();
+    let expected_ascii = expected.render_ascii();
+    for (actual_row, expected_row) in actual_ascii.lines().zip(expected_ascii.lines()) {
+        message.push_str(&format!("{}    {}\n", actual_row, expected_row));
+    }
+
+    let diff = expected.diff(actual);
+    if diff.is_empty() {
+        // the pieces all match, so the boards must disagree about the squares themselves
+        message.push_str("\nall pieces match, the squares themselves differ\n");
+    } else {
+        message.push_str("\ndiffering squares:\n");
+        for square in diff.squares() {
+            let algebraic = CoordinateAlgebraic::from(square.coordinate());
+            let symbol_or_empty = |piece: Option<Piece>| {
+                piece.map_or('.', |piece| piece.symbol())
+            };
+
+            message.push_str(&format!("  {}: actual {}, expected {}\n", algebraic,
+                symbol_or_empty(square.after()), symbol_or_empty(square.before())));
+        }
+    }
+
+    panic!("{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::test_support::assert_board_eq;
+
+    #[test]
+    fn test_assert_board_eq_equal() {
+        assert_board_eq(&Board::new(), &Board::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "e2: actual ., expected P")]
+    fn test_assert_board_eq_reports_squares() {
+        let after_e4 = Board::from_ascii("
+            r n b q k b n r
+            p p p p p p p p
+            . . . . . . . .
+            . . . . . . . .
+            . . . . P . . .
+            . . . . . . . .
+            P P P P . P P P
+            R N B Q K B N R
+        ").unwrap();
+
+        assert_board_eq(&after_e4, &Board::new());
+    }
+}
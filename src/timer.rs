@@ -9,6 +9,22 @@ pub enum TimerDirection {
     Up,
 }
 
+/// Events reported to the callback registered with `set_event_callback`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TimerEvent {
+    /// The player has run out of time
+    Expired { player: PlayerIndex },
+
+    /// The player's remaining time has dropped below the low time threshold
+    LowTime { player: PlayerIndex, remaining_ms: u32 },
+
+    /// The active player has changed
+    Switched { from: PlayerIndex, to: PlayerIndex },
+
+    /// Periodic report of the active player's remaining time, produced by `tick`
+    Tick { player: PlayerIndex, remaining_ms: u32 },
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum TimerError {
     SettingsConflict(String),
@@ -23,10 +39,19 @@ struct ChessTimer<'a> {
     last_player_index: Option<PlayerIndex>,
 
     player_elapsed_ms: [i64; SUPPORTED_PLAYERS],
-    player_maxtime_ms: [u32; SUPPORTED_PLAYERS],
+    /// Time each player is allowed, remaining time is only tracked when this is set (which it
+    /// always is for Down timers) regardless of the counting direction
+    player_maxtime_ms: Option<[u32; SUPPORTED_PLAYERS]>,
     player_adjust_on_switch_ms: [i64; SUPPORTED_PLAYERS],
 
+    /// Time spent by each player on each of their moves, in the order the moves were made
+    move_times: Vec<(PlayerIndex, i64)>,
+
+    /// A LowTime event is raised when a player's remaining time drops below this
+    low_time_threshold_ms: Option<u32>,
+
     callback: Box<dyn FnMut(PlayerIndex) + 'a>,
+    event_callback: Box<dyn FnMut(TimerEvent) + 'a>,
 }
 
 type Result<T> = std::result::Result<T, TimerError>;
@@ -36,17 +61,10 @@ impl<'a> ChessTimer<'a> {
            player_maxtime_ms: Option<[u32; SUPPORTED_PLAYERS]>,
            player_adjust_on_switch_ms: Option<[i64; SUPPORTED_PLAYERS]>) -> Result<ChessTimer<'a>>{
 
-        let player_maxtime_ms = match player_maxtime_ms {
-            Some(maxtime) => maxtime,
-            None => {
-                if direction == TimerDirection::Down {
-                    let string = "Down counting timer requires a maxtime".to_string();
-                    return Err(TimerError::SettingsConflict(string));
-                }
-
-                [0; SUPPORTED_PLAYERS]
-            },
-        };
+        if player_maxtime_ms.is_none() && direction == TimerDirection::Down {
+            let string = "Down counting timer requires a maxtime".to_string();
+            return Err(TimerError::SettingsConflict(string));
+        }
 
         let player_adjust_on_switch_ms = match player_adjust_on_switch_ms {
             Some(adjust) => adjust,
@@ -64,7 +82,10 @@ impl<'a> ChessTimer<'a> {
             player_elapsed_ms: [0; SUPPORTED_PLAYERS],
             player_maxtime_ms,
             player_adjust_on_switch_ms,
+            move_times: Vec::new(),
+            low_time_threshold_ms: None,
             callback: Box::new(|_: PlayerIndex| ()),
+            event_callback: Box::new(|_: TimerEvent| ()),
         })
     }
 
@@ -76,6 +97,41 @@ impl<'a> ChessTimer<'a> {
         (self.callback)(player);
     }
 
+    /// Registers a callback which is told about every TimerEvent, this is independent of (and
+    /// in addition to) the callback registered with `set_callback`
+    pub fn set_event_callback(&mut self, c: impl FnMut(TimerEvent) + 'a) {
+        self.event_callback = Box::new(c);
+    }
+
+    fn trigger_event(&mut self, event: TimerEvent) {
+        (self.event_callback)(event);
+    }
+
+    pub fn set_low_time_threshold(&mut self, threshold_ms: Option<u32>) {
+        self.low_time_threshold_ms = threshold_ms;
+    }
+
+    /// Reports the active player's remaining time (including time used since the last switch)
+    /// as a Tick event. The timer has no clock of its own, so whoever drives it should call
+    /// this as often as they want Tick events. Timers without a maxtime have no remaining time
+    /// to report, so never tick.
+    pub fn tick(&mut self) {
+        if let (Some(current_player), Some(maxtime)) = (self.curr_player_index, self.player_maxtime_ms) {
+            let mut elapsed = self.player_elapsed_ms[current_player];
+
+            // time since the last switch is only committed on the next switch or stop, but
+            // it should still be counted while the timer is running
+            if self.started_at.is_some() {
+                if let Some(last_player_switch_at) = self.last_player_switch_at {
+                    elapsed += last_player_switch_at.elapsed().as_millis() as i64;
+                }
+            }
+
+            let remaining_ms = Self::elapsed_to_remaining(elapsed, maxtime[current_player]);
+            self.trigger_event(TimerEvent::Tick { player: current_player, remaining_ms });
+        }
+    }
+
     pub fn start(&mut self) {
         // capture the time at the start of the function for consistency
         let now = std::time::Instant::now();
@@ -112,11 +168,12 @@ impl<'a> ChessTimer<'a> {
                 self.started_at.unwrap()
             });
 
-            self.adjust_elapsed_time_for_player(current_player, benchmark.elapsed().as_millis() as i64);
+            // invalidate indicators of timer progression before committing, if the commit
+            // expires the player it stops the timer, which must then find it already stopped
+            self.started_at = None;
             self.last_player_switch_at = Some(now);
 
-            // invalidate indicators of timer progression
-            self.started_at = None;
+            self.adjust_elapsed_time_for_player(current_player, benchmark.elapsed().as_millis() as i64);
         }
     }
 
@@ -159,14 +216,30 @@ impl<'a> ChessTimer<'a> {
         Some(self.player_elapsed_ms[player])
     }
 
+    /// Remaining time is reported whenever the timer has a maxtime, whichever direction it
+    /// counts in, and is None for timers without a maxtime
     pub fn check_remaining_time_for_player(&self, player: PlayerIndex) -> Option<u32> {
+        let maxtime = self.player_maxtime_ms?;
+
         // this function call checks that player index is valid, so we don't have to do it
         // elsewhere in this function
-        if let Some(elapsed) = self.check_elapsed_time_for_player(player) {
-            Some(Self::elapsed_to_remaining(elapsed, self.player_maxtime_ms[player]))
-        } else {
-            None
-        }
+        self.check_elapsed_time_for_player(player)
+            .map(|elapsed| Self::elapsed_to_remaining(elapsed, maxtime[player]))
+    }
+
+    /// Same as `check_elapsed_time_for_player` but as a Duration
+    ///
+    /// A Duration can't be negative, so if a player has gained more time than they have used
+    /// (negative elapsed time) this reports zero.
+    pub fn elapsed_duration(&self, player: PlayerIndex) -> Option<std::time::Duration> {
+        self.check_elapsed_time_for_player(player)
+            .map(|elapsed| std::time::Duration::from_millis(elapsed.max(0) as u64))
+    }
+
+    /// Same as `check_remaining_time_for_player` but as a Duration
+    pub fn remaining_duration(&self, player: PlayerIndex) -> Option<std::time::Duration> {
+        self.check_remaining_time_for_player(player)
+            .map(|remaining| std::time::Duration::from_millis(remaining.into()))
     }
 
     pub fn adjust_elapsed_time_for_player(&mut self, player: PlayerIndex, adjustment_ms: i64) {
@@ -175,26 +248,53 @@ impl<'a> ChessTimer<'a> {
             return;
         }
 
-        // adjust player time, then handle side effects,
+        let remaining_before = self.check_remaining_time_for_player(player);
+
+        // adjust player time, then handle side effects
+        self.player_elapsed_ms[player] += adjustment_ms;
+
+        // without a maxtime a player can't run out of time, so there are no side effects
+        let (maxtime, remaining_before) = match (self.player_maxtime_ms, remaining_before) {
+            (Some(maxtime), Some(remaining_before)) => (maxtime[player], remaining_before),
+            _ => return,
+        };
+
         if self.direction == TimerDirection::Down {
             // elapsed time is not allowed to be larger than maxtime for Down count timers
-            self.player_elapsed_ms[player] = std::cmp::min(
-                self.player_maxtime_ms[player].into(),
-                self.player_elapsed_ms[player] + adjustment_ms);
-        } else {
-            self.player_elapsed_ms[player] += adjustment_ms;
+            self.player_elapsed_ms[player] = std::cmp::min(maxtime.into(), self.player_elapsed_ms[player]);
         }
 
         // if the time adjustment makes the elapsed time meet or exceed the maxtime then
-        // this player's time has expired
+        // this player's time has expired, this is only reported the first time it happens
         //
         // `as i64` is safe in this case as we are upcasting from a u32
-        if self.player_elapsed_ms[player] >= self.player_maxtime_ms[player].into() {
+        if self.player_elapsed_ms[player] >= maxtime.into() {
+            if remaining_before == 0 {
+                return;
+            }
+
             self.trigger_callback(player);
+            self.trigger_event(TimerEvent::Expired { player });
             self.stop();
+            return;
+        }
+
+        // only report low time when the threshold is first crossed, not on every adjustment
+        // made while the player is already below it
+        if let Some(threshold) = self.low_time_threshold_ms {
+            let remaining_ms = self.check_remaining_time_for_player(player).unwrap();
+            if remaining_before >= threshold && remaining_ms < threshold {
+                self.trigger_event(TimerEvent::LowTime { player, remaining_ms });
+            }
         }
     }
 
+    /// Returns the time each player spent on each of their moves (in the order the moves were
+    /// made) and clears the recorded history
+    pub fn take_move_times(&mut self) -> Vec<(PlayerIndex, i64)> {
+        std::mem::take(&mut self.move_times)
+    }
+
     pub fn current_player(&self) -> Option<PlayerIndex> { self.curr_player_index }
 
     pub fn switch_to_player(&mut self, player: PlayerIndex) {
@@ -207,12 +307,23 @@ impl<'a> ChessTimer<'a> {
             return;
         }
 
-        // update the statistics of the player we are switching from
-        if let Some(last_player_switch_at) = self.last_player_switch_at {
+        // switching to the player who is already active isn't a change, their clock keeps running
+        if self.curr_player_index == Some(player) {
+            return;
+        }
+
+        // update the statistics of the player we are switching from, but only while the timer
+        // is running, no move is played while it is stopped and stop() already committed the time
+        if let (Some(_), Some(last_player_switch_at)) = (self.started_at, self.last_player_switch_at) {
             if let Some(current_player) = self.curr_player_index {
                 let last_switch = last_player_switch_at.elapsed().as_millis() as i64;
                 let adjust_on_switch = self.player_adjust_on_switch_ms[current_player];
 
+                self.move_times.push((current_player, last_switch));
+
+                // the interval is committed here, so restart it first, otherwise if this expires
+                // the player the resulting stop() would commit the same interval again
+                self.last_player_switch_at = Some(now);
                 self.adjust_elapsed_time_for_player(current_player, last_switch - adjust_on_switch);
             }
         }
@@ -221,6 +332,10 @@ impl<'a> ChessTimer<'a> {
         self.last_player_index = self.curr_player_index;
         self.last_player_switch_at = Some(now);
         self.curr_player_index = Some(player);
+
+        if let Some(from) = self.last_player_index {
+            self.trigger_event(TimerEvent::Switched { from, to: player });
+        }
     }
 
     pub fn switch_to_next_player(&mut self) {
@@ -236,7 +351,7 @@ impl<'a> ChessTimer<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::timer::{ChessTimer, TimerDirection, SUPPORTED_PLAYERS};
+    use crate::timer::{ChessTimer, TimerDirection, TimerEvent, SUPPORTED_PLAYERS};
     use std::time::Duration;
 
     #[test]
@@ -409,4 +524,214 @@ mod tests {
         let remain = timer.check_remaining_time_for_player(0).unwrap();
         assert_gt!(remain, test_maxtime_ms);
     }
+
+    #[test]
+    fn test_take_move_times() {
+        let mut timer = ChessTimer::new(
+            TimerDirection::Down,
+            Some([1000; SUPPORTED_PLAYERS]),
+            None).unwrap();
+
+        // play a few moves, giving each one a little bit of time to register
+        timer.start();
+        for _ in 0..3 {
+            std::thread::sleep(Duration::from_millis(10));
+            timer.switch_to_next_player();
+        }
+
+        // each switch should have recorded one move for the player being switched away from
+        let move_times = timer.take_move_times();
+        let players: Vec<usize> = move_times.iter().map(|(player, _)| *player).collect();
+        assert_eq!(players, vec![0, 1, 0]);
+
+        for (_, move_time) in move_times.iter() {
+            assert_ge!(*move_time, 10);
+        }
+
+        // the history is cleared once taken
+        assert!(timer.take_move_times().is_empty());
+
+        // time spent while the timer is stopped is not a move, and neither is switching to the
+        // player who is already active
+        timer.stop();
+        std::thread::sleep(Duration::from_millis(50));
+        timer.switch_to_next_player();
+        timer.switch_to_player(timer.current_player().unwrap());
+        assert!(timer.take_move_times().is_empty());
+
+        // player 1 is still to move, their elapsed time only counts time while running
+        let elapsed = timer.check_elapsed_time_for_player(1).unwrap();
+        assert_lt!(elapsed, 50);
+    }
+
+    #[test]
+    fn test_duration_queries() {
+        let mut timer = ChessTimer::new(
+            TimerDirection::Down,
+            Some([1000; SUPPORTED_PLAYERS]),
+            None).unwrap();
+
+        timer.adjust_elapsed_time_for_player(0, 250);
+        assert_eq!(timer.elapsed_duration(0), Some(Duration::from_millis(250)));
+        assert_eq!(timer.remaining_duration(0), Some(Duration::from_millis(750)));
+
+        // the Duration values always agree with the millisecond values
+        for player in 0..SUPPORTED_PLAYERS {
+            let elapsed = timer.check_elapsed_time_for_player(player).unwrap();
+            let remaining = timer.check_remaining_time_for_player(player).unwrap();
+
+            assert_eq!(timer.elapsed_duration(player).unwrap().as_millis(), elapsed as u128);
+            assert_eq!(timer.remaining_duration(player).unwrap().as_millis(), remaining as u128);
+        }
+
+        // time gained beyond what was used clamps elapsed to zero, but still counts as remaining
+        timer.adjust_elapsed_time_for_player(1, -500);
+        assert_eq!(timer.elapsed_duration(1), Some(Duration::from_millis(0)));
+        assert_eq!(timer.remaining_duration(1), Some(Duration::from_millis(1500)));
+
+        // invalid players have no times at all
+        assert_eq!(timer.elapsed_duration(SUPPORTED_PLAYERS), None);
+        assert_eq!(timer.remaining_duration(SUPPORTED_PLAYERS), None);
+    }
+
+    #[test]
+    fn test_event_callback_switch() {
+        let mut events = Vec::new();
+
+        {
+            let mut timer = ChessTimer::new(
+                TimerDirection::Down,
+                Some([1000; SUPPORTED_PLAYERS]),
+                None).unwrap();
+
+            timer.set_event_callback(|event| events.push(event));
+            timer.start();
+            timer.switch_to_next_player();
+            timer.switch_to_next_player();
+
+            // switching to the active player doesn't change anything
+            timer.switch_to_player(0);
+        }
+
+        assert_eq!(events, vec![
+            TimerEvent::Switched { from: 0, to: 1 },
+            TimerEvent::Switched { from: 1, to: 0 },
+        ]);
+    }
+
+    #[test]
+    fn test_event_callback_time() {
+        let mut events = Vec::new();
+        let mut expired = Vec::new();
+
+        {
+            let mut timer = ChessTimer::new(
+                TimerDirection::Down,
+                Some([1000; SUPPORTED_PLAYERS]),
+                None).unwrap();
+
+            timer.set_callback(|player| expired.push(player));
+            timer.set_event_callback(|event| events.push(event));
+            timer.set_low_time_threshold(Some(500));
+
+            // a stopped timer ticks with the committed remaining time
+            timer.tick();
+
+            // crossing the threshold reports low time once, further adjustments below it don't
+            timer.adjust_elapsed_time_for_player(0, 600);
+            timer.adjust_elapsed_time_for_player(0, 100);
+
+            // running out of time is reported to both callbacks, but only the first time
+            timer.adjust_elapsed_time_for_player(0, 300);
+            timer.adjust_elapsed_time_for_player(0, 100);
+        }
+
+        assert_eq!(events, vec![
+            TimerEvent::Tick { player: 0, remaining_ms: 1000 },
+            TimerEvent::LowTime { player: 0, remaining_ms: 400 },
+            TimerEvent::Expired { player: 0 },
+        ]);
+        assert_eq!(expired, vec![0]);
+    }
+
+    #[test]
+    fn test_running_timer_expires_on_switch() {
+        let mut events = Vec::new();
+        let mut expired = Vec::new();
+
+        {
+            let mut timer = ChessTimer::new(
+                TimerDirection::Down,
+                Some([5; SUPPORTED_PLAYERS]),
+                None).unwrap();
+
+            timer.set_callback(|player| expired.push(player));
+            timer.set_event_callback(|event| events.push(event));
+
+            // player 0 runs out of time while the clock is running, the expiry is found when
+            // their time is committed on the switch and stops the timer
+            timer.start();
+            std::thread::sleep(Duration::from_millis(20));
+            timer.switch_to_next_player();
+
+            assert_eq!(timer.check_remaining_time_for_player(0), Some(0));
+            assert!(timer.started_at.is_none());
+        }
+
+        assert_eq!(events, vec![
+            TimerEvent::Expired { player: 0 },
+            TimerEvent::Switched { from: 0, to: 1 },
+        ]);
+        assert_eq!(expired, vec![0]);
+    }
+
+    #[test]
+    fn test_up_timer_reporting() {
+        let test_maxtime_ms = 1000;
+        let mut expired = Vec::new();
+        let mut timer = ChessTimer::new(
+            TimerDirection::Up,
+            Some([test_maxtime_ms; SUPPORTED_PLAYERS]),
+            None).unwrap();
+
+        // an Up timer with a maxtime reports both elapsed and remaining time, elapsed should grow
+        // and remaining should shrink as time is used
+        timer.start();
+        std::thread::sleep(Duration::from_millis(10));
+        timer.stop();
+
+        let elapsed = timer.check_elapsed_time_for_player(0).unwrap();
+        let remain = timer.check_remaining_time_for_player(0).unwrap();
+        assert_gt!(elapsed, 0);
+        assert_lt!(remain, test_maxtime_ms);
+
+        timer.start();
+        std::thread::sleep(Duration::from_millis(10));
+        timer.stop();
+
+        assert_gt!(timer.check_elapsed_time_for_player(0).unwrap(), elapsed);
+        assert_lt!(timer.check_remaining_time_for_player(0).unwrap(), remain);
+
+        // and, like a Down timer, it expires at the maxtime
+        timer.set_callback(|player| expired.push(player));
+        timer.adjust_elapsed_time_for_player(1, test_maxtime_ms.into());
+        drop(timer);
+        assert_eq!(expired, vec![1]);
+    }
+
+    #[test]
+    fn test_up_timer_without_maxtime() {
+        let mut expired = Vec::new();
+        let mut timer = ChessTimer::new(TimerDirection::Up, None, None).unwrap();
+        timer.set_callback(|player| expired.push(player));
+
+        // without a maxtime there is no remaining time, and using time never expires the player
+        timer.adjust_elapsed_time_for_player(0, 100 * 1000);
+        assert_eq!(timer.check_elapsed_time_for_player(0), Some(100 * 1000));
+        assert_eq!(timer.check_remaining_time_for_player(0), None);
+        assert_eq!(timer.remaining_duration(0), None);
+
+        drop(timer);
+        assert!(expired.is_empty());
+    }
 }